@@ -0,0 +1,53 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{bail, Context};
+use beholders::{
+    encoding::{bytes_to_field_elements, field_elements_to_bytes},
+    proof::padded_chunk_count,
+    types::TFr,
+};
+use clap::Args as ClapArgs;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The path to the file containing the data
+    #[arg(index = 1)]
+    data: PathBuf,
+
+    /// Where to write the bytes recovered from the field-element encoding
+    #[arg(index = 2)]
+    output: PathBuf,
+
+    /// The number of indices to derive for each Schnorr transcript (must match the value
+    /// passed to `prove`, since it affects the padded chunk count)
+    #[arg(long, default_value_t = 6)]
+    mvalue: usize,
+}
+
+/// Round-trips `data` through [`bytes_to_field_elements`]/[`field_elements_to_bytes`], the
+/// same encoding [`beholders::Proof::prove`] commits to, and writes the recovered bytes to
+/// `output`. Demonstrates that the committed encoding is lossless end to end, since `Proof`
+/// itself only ever holds a sparse, `mvalue`-sized sample of field elements per transcript
+/// rather than the full encoded data.
+pub fn run(args: Args) -> anyhow::Result<()> {
+    let data = fs::read(&args.data).context(format!("Unable to read file: {:?}", args.data))?;
+
+    let chunks = padded_chunk_count(data.len(), args.mvalue);
+    let elements: Vec<TFr> = bytes_to_field_elements(&data, chunks);
+    let recovered = field_elements_to_bytes(&elements);
+
+    if recovered != data {
+        bail!("Recovered bytes do not match the original data");
+    }
+
+    fs::write(&args.output, &recovered)
+        .context(format!("Unable to write file: {:?}", args.output))?;
+
+    println!(
+        "Round-tripped {} bytes through {} field elements",
+        recovered.len(),
+        elements.len()
+    );
+
+    Ok(())
+}
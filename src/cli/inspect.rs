@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+use beholders::{hashing::difficulty, util::read_from_file, Proof};
+use clap::Args as ClapArgs;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The path to the file containing the signature
+    #[arg(index = 1)]
+    signature: PathBuf,
+}
+
+pub fn run(args: Args) -> anyhow::Result<()> {
+    let proof: Proof = read_from_file(&args.signature)?;
+
+    let nfisch = proof.num_base_proofs();
+    let mvalue = proof.mvalue();
+
+    println!("Original data length: {} bytes", proof.original_len);
+    println!("Fischlin iterations (nfisch): {nfisch}");
+    println!("Challenged indices per transcript (mvalue): {mvalue}");
+    println!(
+        "Default bit difficulty for this data length: {}",
+        difficulty(proof.padded_chunks(), nfisch)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use beholders::{
+    types::{TFr, TG1},
+    util::AeadAlgorithm,
+};
+use clap::Args as ClapArgs;
+use kzg_traits::{Fr, G1Mul, G1};
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The path where the secret key will be written
+    #[arg(long)]
+    secret_key: PathBuf,
+
+    /// The path where the public key will be written
+    #[arg(long)]
+    public_key: PathBuf,
+
+    /// Passphrase encrypting the secret key. Prompted interactively if omitted.
+    #[arg(long)]
+    passphrase: Option<String>,
+
+    /// AEAD algorithm sealing the secret key envelope.
+    #[arg(long, value_enum, default_value_t = AeadArg::Aes256Gcm)]
+    aead: AeadArg,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum AeadArg {
+    Aes256Gcm,
+    Chacha20Poly1305,
+}
+
+impl From<AeadArg> for AeadAlgorithm {
+    fn from(value: AeadArg) -> Self {
+        match value {
+            AeadArg::Aes256Gcm => AeadAlgorithm::Aes256Gcm,
+            AeadArg::Chacha20Poly1305 => AeadAlgorithm::ChaCha20Poly1305,
+        }
+    }
+}
+
+pub fn run(args: Args) -> anyhow::Result<()> {
+    let passphrase = match args.passphrase {
+        Some(p) => p,
+        None => rpassword::prompt_password("Secret key passphrase: ")
+            .context("Reading passphrase")?,
+    };
+
+    let sk = TFr::rand();
+    let pk = TG1::generator().mul(&sk);
+
+    beholders::util::write_encrypted_to_file(&args.secret_key, &sk, &passphrase, args.aead.into())
+        .context("Writing encrypted secret key")?;
+    beholders::util::write_to_file(&args.public_key, &pk).context("Writing public key")?;
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+mod extract;
+mod inspect;
+mod keygen;
+mod prove;
+mod setup;
+mod verify;
+
+use clap::Subcommand;
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Generate a Schnorr keypair
+    Keygen(keygen::Args),
+    /// Generate a trusted setup
+    Setup(setup::Args),
+    /// Prove possession of a data blob
+    Prove(prove::Args),
+    /// Verify a beholder signature
+    Verify(verify::Args),
+    /// Print the parameters of a proof/commitment pair
+    Inspect(inspect::Args),
+    /// Round-trip a data file through the field-element encoding, to check it is lossless
+    Extract(extract::Args),
+}
+
+impl Command {
+    pub fn run(self) -> anyhow::Result<()> {
+        match self {
+            Command::Keygen(args) => keygen::run(args),
+            Command::Setup(args) => setup::run(args),
+            Command::Prove(args) => prove::run(args),
+            Command::Verify(args) => verify::run(args),
+            Command::Inspect(args) => inspect::run(args),
+            Command::Extract(args) => extract::run(args),
+        }
+    }
+}
@@ -1,32 +1,30 @@
 use std::{fs, path::PathBuf, time::Instant};
 
-use anyhow::{bail, Context};
+use anyhow::Context;
 use beholders::{
     commitment::TrustedSetup,
     hashing::difficulty,
-    proof::CHUNK_SIZE,
+    proof::padded_chunk_count,
     schnorr::SecretKey,
-    util::{fft_settings, read_from_file, write_to_file},
+    util::{fft_settings, write_to_file},
     Proof,
 };
-use clap::Parser;
+use clap::Args as ClapArgs;
 use humansize::{format_size, BINARY};
 
-// const TRUSTED_SETUP_FILE: &str = "trusted_setup.txt"; // TRUSTED SETUP
-
-#[derive(Parser)]
-struct Cli {
+#[derive(ClapArgs)]
+pub struct Args {
     /// The path to the file containing the data
     #[arg(index = 1)]
-    data: std::path::PathBuf,
+    data: PathBuf,
 
     /// The path where the commitment should be written
     #[arg(index = 2)]
-    commitment: std::path::PathBuf,
+    commitment: PathBuf,
 
     /// The signature output path
     #[arg(index = 3)]
-    signature: std::path::PathBuf,
+    signature: PathBuf,
 
     /// The number of indices to derive for each Schnorr transcript
     #[arg(long, default_value_t = 6)]
@@ -38,38 +36,46 @@ struct Cli {
 
     /// The difficulty of the proof-of-work
     /// (default is 5 + log2(N) - log2(nfisch)),
-    /// where N is the length in chunks of 32 bytes
+    /// where N is the length in encoded field-element chunks
     #[arg(long)]
     bit_difficulty: Option<u32>,
 
-    /// Location of the trusted setup file.
+    /// Location of the trusted setup file. Format is auto-detected from the extension:
+    /// `.txt` for the EIP-4844 hex format, `.json` for a ceremony transcript, otherwise
+    /// the crate's own bincode format.
     #[arg(long)]
     setup_file: PathBuf,
 
     /// Path for the secret key.
     #[arg(long)]
     secret_key: PathBuf,
-}
 
-fn main() -> anyhow::Result<()> {
-    let args = Cli::parse();
+    /// Passphrase decrypting the secret key. Prompted interactively if omitted.
+    #[arg(long)]
+    passphrase: Option<String>,
+}
 
+pub fn run(args: Args) -> anyhow::Result<()> {
     let data = fs::read(&args.data).context(format!("Unable to read file: {:?}", args.data))?;
-    if !data.len().is_power_of_two() {
-        bail!("Data length needs to be a power of two");
-    }
 
     let mvalue = args.mvalue;
     let nfisch = args.nfisch;
 
     println!("File size: {}", format_size(data.len(), BINARY));
-    let chunks = data.len() / CHUNK_SIZE;
-    println!("Num chunks: {chunks}");
+    let chunks = padded_chunk_count(data.len(), mvalue);
+    println!("Num chunks (after padding): {chunks}");
     let bit_difficulty = args
         .bit_difficulty
         .unwrap_or_else(|| difficulty(chunks, nfisch));
 
-    let sk: SecretKey = read_from_file(&args.secret_key)?;
+    let passphrase = match args.passphrase {
+        Some(p) => p,
+        None => rpassword::prompt_password("Secret key passphrase: ")
+            .context("Reading passphrase")?,
+    };
+    let sk: SecretKey =
+        beholders::util::read_encrypted_from_file(&args.secret_key, &passphrase)
+            .context("Reading encrypted secret key")?;
 
     println!(
         "Parameters: nfisch: {}, d: {}, m: {}",
@@ -80,7 +86,7 @@ fn main() -> anyhow::Result<()> {
 
     println!("Loading trusted setup...");
     let fs = fft_settings(chunks).map_err(anyhow::Error::msg)?;
-    let trusted_setup: TrustedSetup = read_from_file(&args.setup_file)?;
+    let trusted_setup: TrustedSetup = TrustedSetup::load_auto(&args.setup_file)?;
 
     println!(
         "Trusted setup: {} {} {}",
@@ -103,8 +109,7 @@ fn main() -> anyhow::Result<()> {
     let (proof, com) = Proof::prove(&kzg_settings, sk, &data, nfisch, bit_difficulty, mvalue)
         .map_err(anyhow::Error::msg)
         .context("KZG error")?;
-    let proof =
-        proof.context("Could not find solve the proof-of-work in the beholder signature")?;
+    let proof = proof.context("Could not solve the proof-of-work in the beholder signature")?;
     let duration = start.elapsed();
     println!("Proving time: {:?}", duration);
 
@@ -112,10 +117,4 @@ fn main() -> anyhow::Result<()> {
     write_to_file(&args.signature, &proof)?;
 
     Ok(())
-
-    // let prover = Prover::<Backend>::new(trusted_setup).unwrap();
-    // let duration = start.elapsed();
-
-    // println!("Initialization time: {:?}", duration);
-    // prover.prove(&data);
 }
@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+use beholders::commitment::TrustedSetup;
+use clap::Args as ClapArgs;
+use kzg_traits::eth;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The path where the trusted setup will be written
+    #[arg(index = 1)]
+    output: PathBuf,
+
+    /// The number of secrets to generate
+    #[arg(long)]
+    secrets: usize,
+}
+
+pub fn run(args: Args) -> anyhow::Result<()> {
+    assert!(
+        args.secrets.is_power_of_two(),
+        "Secrets length needs to be a power of two",
+    );
+    assert!(
+        args.secrets >= eth::FIELD_ELEMENTS_PER_CELL,
+        "Secrets length needs to be at least {}",
+        eth::FIELD_ELEMENTS_PER_CELL
+    );
+
+    let g2_len = eth::TRUSTED_SETUP_NUM_G2_POINTS;
+    let (g1_monomial, g1_lagrange, mut g2_monomial) =
+        kzg::utils::generate_trusted_setup(args.secrets, [1; 32]);
+    g2_monomial.truncate(g2_len);
+    let setup = TrustedSetup {
+        g1_monomial,
+        g1_lagrange,
+        g2_monomial,
+    };
+
+    beholders::util::write_to_file(&args.output, &setup)?;
+
+    Ok(())
+}
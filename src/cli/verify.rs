@@ -4,15 +4,14 @@ use anyhow::bail;
 use beholders::{
     commitment::{Commitment, TrustedSetup},
     hashing::difficulty,
-    proof::CHUNK_SIZE,
     schnorr::PublicKey,
     util::{fft_settings, read_from_file},
     Proof,
 };
-use clap::Parser;
+use clap::Args as ClapArgs;
 
-#[derive(Parser)]
-struct Cli {
+#[derive(ClapArgs)]
+pub struct Args {
     /// The path to the file containing the commitment
     #[arg(index = 1)]
     commitment: PathBuf,
@@ -34,11 +33,9 @@ struct Cli {
     #[arg(long)]
     bit_difficulty: Option<u32>,
 
-    /// Length of the data, in bytes.
-    #[arg(long)]
-    data_len: usize,
-
-    /// Location of the trusted setup file.
+    /// Location of the trusted setup file. Format is auto-detected from the extension:
+    /// `.txt` for the EIP-4844 hex format, `.json` for a ceremony transcript, otherwise
+    /// the crate's own bincode format.
     #[arg(long)]
     setup_file: PathBuf,
 
@@ -47,18 +44,19 @@ struct Cli {
     public_key: PathBuf,
 }
 
-fn main() -> anyhow::Result<()> {
-    let args = Cli::parse();
+pub fn run(args: Args) -> anyhow::Result<()> {
     let pk: PublicKey = read_from_file(&args.public_key)?;
+    let proof: Proof = read_from_file(&args.signature)?;
+    let commitment: Commitment = read_from_file(&args.commitment)?;
 
-    let chunks = args.data_len / CHUNK_SIZE;
+    let chunks = proof.padded_chunks();
     let nfisch = args.nfisch;
     let bit_difficulty = args
         .bit_difficulty
         .unwrap_or_else(|| difficulty(chunks, nfisch));
 
     println!("Loading trusted setup");
-    let trusted_setup: TrustedSetup = read_from_file(&args.setup_file)?;
+    let trusted_setup: TrustedSetup = TrustedSetup::load_auto(&args.setup_file)?;
     let fs = fft_settings(chunks).map_err(anyhow::Error::msg)?;
     let kzg_settings = trusted_setup
         .into_kzg_settings(&fs)
@@ -66,18 +64,8 @@ fn main() -> anyhow::Result<()> {
 
     println!("Done loading trusted setup");
 
-    let proof: Proof = read_from_file(&args.signature)?;
-    let commitment: Commitment = read_from_file(&args.commitment)?;
-
     let output = proof
-        .verify(
-            &pk,
-            &commitment,
-            chunks,
-            &kzg_settings,
-            bit_difficulty,
-            args.mvalue,
-        )
+        .verify(&pk, &commitment, &kzg_settings, bit_difficulty, args.mvalue)
         .expect("KZG error");
     match output {
         true => {
@@ -1,24 +1,30 @@
-use std::time::Instant;
+use alloc::{string::String, vec::Vec};
 
-use kzg_traits::{eth, FFTFr, FFTSettings, FK20SingleSettings, Fr, KZGSettings, Poly};
+use kzg_traits::{
+    eth, EcBackend, FFTFr, FFTSettings, FK20SingleSettings, Fr, G1Mul, KZGSettings, Poly, G1, G2,
+};
 use serde::{Deserialize, Serialize};
 
-use crate::types::{TFFTSettings, TFK20SingleSettings, TFr, TKZGSettings, TPoly, TG1, TG2};
+use crate::debug_log;
+use crate::hashing::{batch_challenges, Prelude};
+use crate::schnorr::Schnorr;
+use crate::types::{Backend, DefaultBackend, TFK20SingleSettings, TFr};
 
 /// KZG opening
-pub type Opening = TG1;
+pub type Opening<B = DefaultBackend> = <B as EcBackend>::G1;
 /// Polynomial Commitment (KZG) value
-pub type Commitment = TG1;
+pub type Commitment<B = DefaultBackend> = <B as EcBackend>::G1;
 
 #[derive(Debug, Serialize, Deserialize)]
-pub struct TrustedSetup {
-    pub g1_monomial: Vec<TG1>,
-    pub g1_lagrange: Vec<TG1>,
-    pub g2_monomial: Vec<TG2>,
+#[serde(bound = "")]
+pub struct TrustedSetup<B: EcBackend = DefaultBackend> {
+    pub g1_monomial: Vec<B::G1>,
+    pub g1_lagrange: Vec<B::G1>,
+    pub g2_monomial: Vec<B::G2>,
 }
 
-impl TrustedSetup {
-    pub fn from_kzg_settings(kzg_settings: TKZGSettings) -> Self {
+impl<B: EcBackend> TrustedSetup<B> {
+    pub fn from_kzg_settings(kzg_settings: B::KZGSettings) -> Self {
         Self {
             g1_monomial: kzg_settings.g1_values_monomial,
             g1_lagrange: kzg_settings.g1_values_lagrange_brp,
@@ -26,8 +32,8 @@ impl TrustedSetup {
         }
     }
 
-    pub fn into_kzg_settings(self, fs: &TFFTSettings) -> Result<TKZGSettings, String> {
-        TKZGSettings::new(
+    pub fn into_kzg_settings(self, fs: &B::FFTSettings) -> Result<B::KZGSettings, String> {
+        B::KZGSettings::new(
             &self.g1_monomial,
             &self.g1_lagrange,
             &self.g2_monomial,
@@ -35,6 +41,116 @@ impl TrustedSetup {
             eth::FIELD_ELEMENTS_PER_CELL,
         )
     }
+
+    /// Loads a trusted setup, auto-detecting the on-disk format from `path`'s extension:
+    /// `.txt` is parsed as the canonical EIP-4844 hex format via
+    /// [`from_eip4844_text`](Self::from_eip4844_text), `.json` as a ceremony transcript via
+    /// [`from_ceremony_json`](Self::from_ceremony_json), and anything else falls back to the
+    /// crate's own bincode format via [`crate::util::read_from_file`].
+    #[cfg(feature = "std")]
+    pub fn load_auto(path: &std::path::Path) -> anyhow::Result<Self>
+    where
+        Self: serde::de::DeserializeOwned,
+    {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("txt") => Self::from_eip4844_text(path),
+            Some("json") => Self::from_ceremony_json(path),
+            _ => crate::util::read_from_file(path),
+        }
+    }
+
+    /// Parses the canonical EIP-4844 `trusted_setup.txt` hex-per-line format: a line with
+    /// the number of G1 points, a line with the number of G2 points, then that many
+    /// hex-encoded G1 monomial points, that many hex-encoded G1 Lagrange (bit-reversal
+    /// permuted) points, and finally the G2 monomial points, one point per line.
+    #[cfg(feature = "std")]
+    pub fn from_eip4844_text(path: &std::path::Path) -> anyhow::Result<Self> {
+        use anyhow::Context;
+
+        let text = std::fs::read_to_string(path)
+            .context(format!("Unable to read trusted setup file: {:?}", path))?;
+        let mut lines = text.lines();
+
+        let num_g1: usize = lines
+            .next()
+            .context("Missing G1 point count")?
+            .trim()
+            .parse()
+            .context("Invalid G1 point count")?;
+        let num_g2: usize = lines
+            .next()
+            .context("Missing G2 point count")?
+            .trim()
+            .parse()
+            .context("Invalid G2 point count")?;
+
+        let mut next_g1 = |section: &str| -> anyhow::Result<B::G1> {
+            let line = lines
+                .next()
+                .context(format!("Truncated {section} section"))?
+                .trim()
+                .trim_start_matches("0x");
+            let bytes = hex::decode(line).context(format!("Invalid hex in {section} section"))?;
+            B::G1::from_bytes(&bytes).map_err(|e| anyhow::anyhow!("Invalid {section} point: {e}"))
+        };
+        let g1_monomial = (0..num_g1).map(|_| next_g1("G1 monomial")).collect::<anyhow::Result<_>>()?;
+        let g1_lagrange = (0..num_g1).map(|_| next_g1("G1 Lagrange")).collect::<anyhow::Result<_>>()?;
+
+        let g2_monomial = (0..num_g2)
+            .map(|_| {
+                let line = lines
+                    .next()
+                    .context("Truncated G2 monomial section")?
+                    .trim()
+                    .trim_start_matches("0x");
+                let bytes = hex::decode(line).context("Invalid hex in G2 monomial section")?;
+                B::G2::from_bytes(&bytes).map_err(|e| anyhow::anyhow!("Invalid G2 monomial point: {e}"))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self {
+            g1_monomial,
+            g1_lagrange,
+            g2_monomial,
+        })
+    }
+
+    /// Loads a trusted setup from a KZG ceremony transcript JSON file, e.g. the output of
+    /// a powers-of-tau ceremony, with `g1_monomial`/`g1_lagrange`/`g2_monomial` arrays of
+    /// `0x`-prefixed hex points mirroring this type's own fields.
+    #[cfg(feature = "std")]
+    pub fn from_ceremony_json(path: &std::path::Path) -> anyhow::Result<Self> {
+        use anyhow::Context;
+
+        #[derive(serde::Deserialize)]
+        struct CeremonyTranscript {
+            g1_monomial: Vec<String>,
+            g1_lagrange: Vec<String>,
+            g2_monomial: Vec<String>,
+        }
+
+        let text = std::fs::read_to_string(path)
+            .context(format!("Unable to read ceremony transcript: {:?}", path))?;
+        let transcript: CeremonyTranscript =
+            serde_json::from_str(&text).context("Invalid ceremony transcript JSON")?;
+
+        let decode_g1 = |point: &str| -> anyhow::Result<B::G1> {
+            let bytes = hex::decode(point.trim_start_matches("0x"))
+                .context("Invalid hex G1 point in ceremony transcript")?;
+            B::G1::from_bytes(&bytes).map_err(|e| anyhow::anyhow!("Invalid G1 point: {e}"))
+        };
+        let decode_g2 = |point: &str| -> anyhow::Result<B::G2> {
+            let bytes = hex::decode(point.trim_start_matches("0x"))
+                .context("Invalid hex G2 point in ceremony transcript")?;
+            B::G2::from_bytes(&bytes).map_err(|e| anyhow::anyhow!("Invalid G2 point: {e}"))
+        };
+
+        Ok(Self {
+            g1_monomial: transcript.g1_monomial.iter().map(|p| decode_g1(p)).collect::<anyhow::Result<_>>()?,
+            g1_lagrange: transcript.g1_lagrange.iter().map(|p| decode_g1(p)).collect::<anyhow::Result<_>>()?,
+            g2_monomial: transcript.g2_monomial.iter().map(|p| decode_g2(p)).collect::<anyhow::Result<_>>()?,
+        })
+    }
 }
 
 pub(crate) fn interpolate<TFr, TFFT, TPoly>(settings: &TFFT, data: &[TFr]) -> TPoly
@@ -57,15 +173,16 @@ pub(crate) fn get_point<TFr: Fr>(
     &roots[i * stride]
 }
 
-pub fn open_all_fk20(
-    kzg_settings: &TKZGSettings,
-    data: &[TFr],
-) -> Result<(Commitment, Vec<Opening>), String> {
-    let start = Instant::now();
+pub fn open_all_fk20<B: Backend>(
+    kzg_settings: &B::KZGSettings,
+    data: &[B::Fr],
+) -> Result<(Commitment<B>, Vec<Opening<B>>), String> {
+    #[cfg(feature = "std")]
+    let start = std::time::Instant::now();
 
     let fft_settings = kzg_settings.get_fft_settings();
-    let fk20_settings = TFK20SingleSettings::new(kzg_settings, 2 * data.len())?;
-    let poly: TPoly = interpolate(fft_settings, data);
+    let fk20_settings = B::FK20Settings::new(kzg_settings, 2 * data.len())?;
+    let poly: B::Poly = interpolate(fft_settings, data);
     let com = kzg_settings.commit_to_poly(&poly)?;
     let fk20 = fk20_settings.data_availability_optimized(&poly)?;
     let openings = fk20
@@ -75,14 +192,59 @@ pub fn open_all_fk20(
         .map(|(_, x)| x)
         .collect();
 
-    let duration = start.elapsed();
-    println!("FK20 time: {:?}", duration);
+    #[cfg(feature = "std")]
+    debug_log!("FK20 time: {:?}", start.elapsed());
     Ok((com, openings))
 }
 
+/// Verifies a batch of KZG openings against `com` with a single pairing check.
+///
+/// Each entry is `(x, y, opening)`: the evaluation point, the claimed value and the
+/// single-point FK20 opening proof. Challenges are derived from `transcript_seed` and
+/// `schnorr` (the base proof's Fischlin prelude and its `(a, c, z)`) via
+/// [`batch_challenges`], so this collapses the per-index `check_proof_single` loop in
+/// [`crate::proof::BaseProof::verify`] into one pairing product rather than
+/// `openings.len()` of them.
+pub(crate) fn verify_openings_batched<B: Backend>(
+    kzg_settings: &B::KZGSettings,
+    com: &Commitment<B>,
+    transcript_seed: Prelude,
+    schnorr: &Schnorr<B>,
+    openings: &[(B::Fr, B::Fr, Opening<B>)],
+) -> Result<bool, String> {
+    let Some((first, rest)) = openings.split_first() else {
+        return Ok(true);
+    };
+
+    let points: Vec<_> = openings.iter().map(|(x, y, _)| (*x, *y)).collect();
+    let gammas = batch_challenges::<B>(transcript_seed, schnorr, &points);
+    let (first_gamma, rest_gammas) = gammas.split_first().expect("openings is non-empty");
+
+    let (x0, y0, pi0) = first;
+    let mut gamma_sum = *first_gamma;
+    let mut acc_y = first_gamma.mul(y0);
+    let mut f = pi0.mul(first_gamma);
+    let mut extra = pi0.mul(&first_gamma.mul(x0));
+
+    for ((x, y, pi), gamma) in rest.iter().zip(rest_gammas) {
+        gamma_sum = gamma_sum.add(gamma);
+        acc_y = acc_y.add(&gamma.mul(y));
+        f = f.add(&pi.mul(gamma));
+        extra = extra.add(&pi.mul(&gamma.mul(x)));
+    }
+
+    let g1 = B::G1::generator();
+    let e = com.mul(&gamma_sum).add(&g1.mul(&acc_y.negate())).add(&extra);
+
+    let h = &kzg_settings.g2_values_monomial[0];
+    let s2 = &kzg_settings.g2_values_monomial[1];
+
+    Ok(B::verify_pairing(&f, s2, &e, h))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::types::TFFTSettings;
+    use crate::types::{TFFTSettings, TKZGSettings, TPoly};
 
     use super::*;
     use kzg::{
@@ -268,7 +430,7 @@ mod tests {
             .map(TFr::from_u64)
             .collect();
 
-        let (_com, all_proofs) = open_all_fk20(&ks, &data).unwrap();
+        let (_com, all_proofs) = open_all_fk20::<DefaultBackend>(&ks, &data).unwrap();
         let direct = open_all(&ks, &data).unwrap();
         assert_eq!(all_proofs, direct);
     }
@@ -0,0 +1,76 @@
+//! Canonical byte <-> field-element encoding.
+//!
+//! A raw 32-byte little-endian chunk reinterpreted as a BLS12-381 scalar can be `>=` the
+//! scalar field modulus (which sits just under `2^255`), so [`Fr::from_bytes`] would reject
+//! it or [`Fr::from_bytes_unchecked`] would silently reduce it, corrupting the committed
+//! data. Packing [`BYTES_PER_FIELD_ELEMENT`] (31) bytes per element instead keeps every
+//! chunk a canonical scalar. The byte length is carried as the first field element so
+//! [`field_elements_to_bytes`] can strip the zero padding and losslessly recover the
+//! original bytes.
+
+use alloc::vec::Vec;
+use kzg_traits::Fr;
+
+/// Number of data bytes packed into each field element. 31, not 32: the BLS12-381 scalar
+/// field modulus is just under `2^255`, so a full 32-byte chunk could be `>=` the modulus.
+pub const BYTES_PER_FIELD_ELEMENT: usize = 31;
+
+/// Encodes `data` as field elements: one length-prefix element followed by `data` packed
+/// [`BYTES_PER_FIELD_ELEMENT`] bytes per element (zero-padded in the last element), then
+/// zero-padded with empty elements out to `min_len` elements.
+pub fn bytes_to_field_elements<F: Fr>(data: &[u8], min_len: usize) -> Vec<F> {
+    let mut elements = Vec::with_capacity(min_len.max(1));
+    elements.push(F::from_u64(data.len() as u64));
+    elements.extend(data.chunks(BYTES_PER_FIELD_ELEMENT).map(|chunk| {
+        let mut buf = [0u8; 32];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        F::from_bytes_unchecked(&buf)
+            .expect("31 data bytes followed by a zero byte is always a canonical scalar")
+    }));
+    elements.resize(elements.len().max(min_len), F::zero());
+    elements
+}
+
+/// Inverse of [`bytes_to_field_elements`]: reads the length prefix back off `elements` and
+/// returns exactly the original bytes, with padding stripped.
+pub fn field_elements_to_bytes<F: Fr>(elements: &[F]) -> Vec<u8> {
+    let (len_element, chunks) = elements
+        .split_first()
+        .expect("elements always has at least the length-prefix element");
+    let len = u64::from_le_bytes(len_element.to_bytes()[..8].try_into().unwrap()) as usize;
+
+    let mut out = Vec::with_capacity(len);
+    for chunk in chunks {
+        out.extend_from_slice(&chunk.to_bytes()[..BYTES_PER_FIELD_ELEMENT]);
+    }
+    out.truncate(len);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use kzg::types::fr::FsFr;
+
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_short_data() {
+        let data = b"hello beholder";
+        let elements: Vec<FsFr> = bytes_to_field_elements(data, 0);
+        assert_eq!(field_elements_to_bytes(&elements), data);
+    }
+
+    #[test]
+    fn test_roundtrip_pads_to_min_len() {
+        let data = [7u8; 100];
+        let elements: Vec<FsFr> = bytes_to_field_elements(&data, 8);
+        assert_eq!(elements.len(), 8);
+        assert_eq!(field_elements_to_bytes(&elements), data);
+    }
+
+    #[test]
+    fn test_roundtrip_empty_data() {
+        let elements: Vec<FsFr> = bytes_to_field_elements(&[], 0);
+        assert_eq!(field_elements_to_bytes(&elements), Vec::<u8>::new());
+    }
+}
@@ -1,4 +1,6 @@
-use kzg_traits::{Fr, G1};
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use kzg_traits::{EcBackend, Fr, G1};
 // use log::debug;
 use sha2::{
     compress512,
@@ -8,13 +10,16 @@ use sha2::{
 use crate::{
     commitment::Commitment,
     schnorr::{PublicKey, Schnorr},
-    types::{TFr, TG1},
 };
 
-pub(crate) type HashOutput = [u64; 8];
+pub type HashOutput = [u64; 8];
 pub(crate) type Prelude = HashOutput;
 
-pub(crate) fn prelude(pk: &PublicKey, com: &Commitment, a_i: impl Iterator<Item = TG1>) -> Prelude {
+pub(crate) fn prelude<B: EcBackend>(
+    pk: &PublicKey<B>,
+    com: &Commitment<B>,
+    a_i: impl Iterator<Item = B::G1>,
+) -> Prelude {
     use sha2::Digest;
     let input = vec![*pk, *com].into_iter().chain(a_i);
     let bytes: Vec<u8> = input.flat_map(|x| x.to_bytes()).collect();
@@ -22,6 +27,67 @@ pub(crate) fn prelude(pk: &PublicKey, com: &Commitment, a_i: impl Iterator<Item
     bytemuck::cast(hash)
 }
 
+/// Deterministic stream of 16-bit words derived from `(i, c)`, used as the entropy source
+/// for sampling without replacement in [`derive_indices`].
+///
+/// The first block reproduces the single `compress512` call the old with-replacement
+/// `derive_indices` made; once its 32 words are exhausted, a counter block (appended after
+/// `c`) is bumped and re-compressed to draw more, so `derive_indices` isn't capped at 32
+/// distinct indices.
+struct WordStream {
+    input: [u8; 128],
+    counter: u64,
+    words: [u16; 32],
+    pos: usize,
+}
+
+impl WordStream {
+    fn new(i: usize, c: &impl Fr) -> Self {
+        let mut input = [0u8; 128];
+        input[0..8].clone_from_slice(&i.to_le_bytes());
+        input[8..40].clone_from_slice(&c.to_bytes());
+
+        let mut stream = Self {
+            input,
+            counter: 0,
+            words: [0u16; 32],
+            pos: 32,
+        };
+        stream.refill();
+        stream
+    }
+
+    fn refill(&mut self) {
+        self.input[40..48].clone_from_slice(&self.counter.to_le_bytes());
+        self.counter += 1;
+
+        let mut state = [0u64; 8];
+        let blocks: &GenericArray<_, U128> = GenericArray::from_slice(&self.input);
+        compress512(&mut state, &[*blocks]);
+
+        self.words = bytemuck::cast(state);
+        self.pos = 0;
+    }
+
+    fn next_word(&mut self) -> u16 {
+        if self.pos == self.words.len() {
+            self.refill();
+        }
+        let word = self.words[self.pos];
+        self.pos += 1;
+        word
+    }
+}
+
+/// Samples `num_indices` *distinct* positions in `0..data_len`, deterministically for a
+/// given `(i, c)`.
+///
+/// Uses a partial Fisher–Yates selection driven by [`WordStream`]: at step `j` a word is
+/// drawn and reduced mod `data_len - j`, then swap-selected against a lazily-materialized
+/// permutation (`pool` only stores entries that differ from the identity, rather than
+/// eagerly allocating all of `0..data_len`). Without this, `x % data_len` can repeat the
+/// same block when `data_len` is small or `num_indices` is large, under-challenging the
+/// proof-of-retrievability.
 pub(crate) fn derive_indices(
     i: usize,
     c: &impl Fr,
@@ -37,24 +103,28 @@ pub(crate) fn derive_indices(
         num_indices <= 32,
         "At most 32 indices per transcript supported"
     );
+    assert!(
+        num_indices <= data_len,
+        "Cannot sample {num_indices} distinct indices out of {data_len}"
+    );
 
-    let mut state = [0u64; 8];
-    let mut input = [0u8; 128];
-    input[0..8].clone_from_slice(&i.to_le_bytes());
-    input[8..40].clone_from_slice(&c.to_bytes());
+    let mut words = WordStream::new(i, c);
+    let mut pool: BTreeMap<usize, usize> = BTreeMap::new();
+    let mut indices = Vec::with_capacity(num_indices);
 
-    let blocks: &GenericArray<_, U128> = GenericArray::from_slice(&input);
-    compress512(&mut state, &[*blocks]);
+    for step in 0..num_indices {
+        let remaining = data_len - step;
+        let r = (words.next_word() as usize) % remaining;
 
-    let state: [u16; 32] = bytemuck::cast(state);
-    state
-        .map(|x| {
-            let x: usize = x.into();
-            x % data_len
-        })
-        .into_iter()
-        .take(num_indices)
-        .collect()
+        let selected = *pool.get(&r).unwrap_or(&r);
+        let last = remaining - 1;
+        let last_val = *pool.get(&last).unwrap_or(&last);
+        pool.insert(r, last_val);
+
+        indices.push(selected);
+    }
+
+    indices
 }
 
 // prelude: 32 bytes
@@ -64,12 +134,12 @@ pub(crate) fn derive_indices(
 // val: 32 bytes
 // opening: 48 bytes
 // TOTAL:
-pub(crate) fn individual_hash(
+pub(crate) fn individual_hash<B: EcBackend>(
     prelude: Prelude,
-    schnorr: &Schnorr,
+    schnorr: &Schnorr<B>,
     fisch_iter: usize,
     k: u8,
-    val: TFr,
+    val: B::Fr,
     opening: &impl G1,
 ) -> HashOutput {
     let fisch_iter: u16 = fisch_iter
@@ -94,6 +164,40 @@ pub(crate) fn individual_hash(
     state
 }
 
+/// Derives the Fiat–Shamir challenges `gamma_i` used to batch several KZG openings
+/// against the same commitment into a single pairing check.
+///
+/// Each `gamma_i` is bound to `seed` (the beholder signature's prelude), `schnorr` (that
+/// transcript's `(a, c, z)`), and the claimed `(x_i, y_i)` pair, so a verifier re-derives
+/// the same challenges from the proof alone and a prover can't pick openings after the
+/// challenges are known.
+pub(crate) fn batch_challenges<B: EcBackend>(
+    seed: Prelude,
+    schnorr: &Schnorr<B>,
+    points: &[(B::Fr, B::Fr)],
+) -> Vec<B::Fr> {
+    use sha2::Digest;
+
+    let Schnorr { a, c, z } = schnorr;
+
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, (x, y))| {
+            let mut hasher = sha2::Sha512::new();
+            hasher.update(bytemuck::cast::<_, [u8; 64]>(seed));
+            hasher.update(a.to_bytes());
+            hasher.update(c.to_le_bytes());
+            hasher.update(z.to_bytes());
+            hasher.update((i as u64).to_le_bytes());
+            hasher.update(x.to_bytes());
+            hasher.update(y.to_bytes());
+            let digest: [u8; 64] = hasher.finalize().into();
+            B::Fr::from_bytes_unchecked(&digest[..32]).expect("digest prefix is 32 bytes")
+        })
+        .collect()
+}
+
 /// Returns true if `hash_output` has at least `difficulty` leading zeros (little-endian) / trailing zeros (big-endian).
 pub(crate) fn pow_pass(hash_output: &HashOutput, difficulty: u32) -> bool {
     assert!(difficulty <= 64, "Only difficulty <= 64 is supported");
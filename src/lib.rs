@@ -1,12 +1,22 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(
     clippy::unsafe_derive_deserialize,
     clippy::cloned_instead_of_copied,
     clippy::explicit_iter_loop
 )]
 #![allow(clippy::too_many_arguments)]
+#[macro_use]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
 pub mod commitment;
+pub mod encoding;
 mod hashing;
+pub mod mmr;
 pub mod proof;
+pub mod recovery;
 mod schnorr;
 pub mod types;
 pub mod util;
@@ -0,0 +1,427 @@
+//! Append-only commitment accumulator backed by a Merkle Mountain Range (MMR).
+//!
+//! Leaves are `H(commitment)`; internal nodes are `H(left ‖ right)`. After `n` appends the
+//! accumulator is a forest of perfectly-balanced binary trees ("peaks"), one per set bit of
+//! `n`, which [`Mmr::root`] bags right-to-left into a single [`HashOutput`]. [`MmrPeaks`] is
+//! the durable snapshot: the peak values plus the leaf count are all that's needed to resume
+//! appending and to recompute the root, so that's what gets persisted via
+//! [`crate::util::write_to_file`]/[`crate::util::read_from_file`]. The per-leaf sibling
+//! history needed for [`Mmr::inclusion_proof`] is kept in memory only; an [`Mmr`] rebuilt via
+//! [`Mmr::from_peaks`] can keep appending and recompute the root, but can no longer produce
+//! inclusion proofs.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use kzg_traits::G1;
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+
+use crate::commitment::Commitment;
+pub use crate::hashing::HashOutput;
+use crate::types::{Backend, DefaultBackend};
+
+fn leaf_hash<B: Backend>(commitment: &Commitment<B>) -> HashOutput {
+    let hash: [u8; 64] = sha2::Sha512::digest(commitment.to_bytes()).into();
+    bytemuck::cast(hash)
+}
+
+fn node_hash(left: HashOutput, right: HashOutput) -> HashOutput {
+    let mut hasher = sha2::Sha512::new();
+    hasher.update(bytemuck::cast::<_, [u8; 64]>(left));
+    hasher.update(bytemuck::cast::<_, [u8; 64]>(right));
+    let hash: [u8; 64] = hasher.finalize().into();
+    bytemuck::cast(hash)
+}
+
+/// Heights of the set bits of `n`, tallest (most-significant) first.
+fn height_bits(n: u64) -> impl Iterator<Item = usize> {
+    (0..u64::BITS as usize).rev().filter(move |&h| n & (1u64 << h) != 0)
+}
+
+/// Which side of a hash combination a proof element sits on, relative to the running hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+fn combine(side: Side, other: HashOutput, acc: HashOutput) -> HashOutput {
+    match side {
+        Side::Left => node_hash(other, acc),
+        Side::Right => node_hash(acc, other),
+    }
+}
+
+/// Bags `peaks` right-to-left into a single hash, per [`Mmr::root`].
+fn bag(peaks: &[HashOutput]) -> Option<HashOutput> {
+    let mut iter = peaks.iter().rev();
+    let mut acc = *iter.next()?;
+    for &p in iter {
+        acc = node_hash(p, acc);
+    }
+    Some(acc)
+}
+
+/// The proof elements needed to fold `peaks[pi]` (each paired with its leaf-width) into the
+/// root of a right-to-left bagging of `peaks`.
+///
+/// Each `Side::Left` entry also carries the width (leaf count) of the taller peak it
+/// combines in, so [`verify_inclusion`] can sum them up into `leaves_before` (how many
+/// leaves precede `pi`'s peak) without needing the accumulator's full leaf count.
+fn bagging_path(peaks: &[(HashOutput, u64)], pi: usize) -> Vec<(Side, HashOutput, u64)> {
+    let mut path = Vec::new();
+    let right = &peaks[pi + 1..];
+    let right_hashes: Vec<HashOutput> = right.iter().map(|&(h, _)| h).collect();
+    if let Some(right_acc) = bag(&right_hashes) {
+        let right_width: u64 = right.iter().map(|&(_, w)| w).sum();
+        path.push((Side::Right, right_acc, right_width));
+    }
+    for &(p, w) in peaks[..pi].iter().rev() {
+        path.push((Side::Left, p, w));
+    }
+    path
+}
+
+/// An inclusion proof for one leaf of an [`Mmr`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InclusionProof {
+    /// Sibling hashes from the leaf up to its peak's root.
+    pub siblings: Vec<(Side, HashOutput)>,
+    /// Hashes needed to bag the leaf's peak into the overall root, each paired with the
+    /// leaf-width of the peak (or bagged group of shorter peaks) it combines in.
+    pub peak_bagging: Vec<(Side, HashOutput, u64)>,
+}
+
+/// Durable snapshot of an [`Mmr`]: the current peaks plus the leaf count.
+///
+/// This is enough to resume appending and to recompute [`Mmr::root`], but not to produce
+/// inclusion proofs for the leaves that were folded into the snapshot's peaks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MmrPeaks {
+    pub leaf_count: u64,
+    /// Peak hashes, tallest peak (highest set bit of `leaf_count`) first.
+    pub peaks: Vec<HashOutput>,
+}
+
+/// Append-only Merkle Mountain Range accumulator of [`Commitment`]s.
+#[derive(Debug)]
+pub struct Mmr<B: Backend = DefaultBackend> {
+    /// `levels[h]` holds every height-`h` node hash produced since this accumulator was
+    /// constructed, oldest first; empty when rebuilt via [`Mmr::from_peaks`].
+    levels: Vec<Vec<HashOutput>>,
+    leaf_count: u64,
+    /// `false` for an accumulator rebuilt from a snapshot, which no longer has the sibling
+    /// history [`Mmr::inclusion_proof`] needs.
+    supports_proofs: bool,
+    _backend: PhantomData<B>,
+}
+
+impl<B: Backend> Default for Mmr<B> {
+    fn default() -> Self {
+        Self {
+            levels: Vec::new(),
+            leaf_count: 0,
+            supports_proofs: true,
+            _backend: PhantomData,
+        }
+    }
+}
+
+impl<B: Backend> Mmr<B> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuilds an accumulator from a persisted [`MmrPeaks`] snapshot.
+    ///
+    /// The result can append new leaves and compute `root()` correctly, but
+    /// [`Mmr::inclusion_proof`] always returns `None` on it: the sibling history beneath the
+    /// snapshot's peaks wasn't persisted.
+    pub fn from_peaks(snapshot: MmrPeaks) -> Self {
+        let mut levels = Vec::new();
+        for (h, &peak) in height_bits(snapshot.leaf_count).zip(snapshot.peaks.iter()) {
+            if levels.len() <= h {
+                levels.resize(h + 1, Vec::new());
+            }
+            levels[h].push(peak);
+        }
+        Self {
+            levels,
+            leaf_count: snapshot.leaf_count,
+            supports_proofs: false,
+            _backend: PhantomData,
+        }
+    }
+
+    /// Appends `commitment`, returning its leaf index. Never rewrites an existing node.
+    pub fn append(&mut self, commitment: &Commitment<B>) -> u64 {
+        let leaf_index = self.leaf_count;
+        self.leaf_count += 1;
+
+        let mut hash = leaf_hash::<B>(commitment);
+        let mut height = 0usize;
+        loop {
+            if self.levels.len() == height {
+                self.levels.push(Vec::new());
+            }
+            self.levels[height].push(hash);
+            if self.levels[height].len() % 2 != 0 {
+                break;
+            }
+            let level = &self.levels[height];
+            let (left, right) = (level[level.len() - 2], level[level.len() - 1]);
+            hash = node_hash(left, right);
+            height += 1;
+        }
+
+        leaf_index
+    }
+
+    /// Current peak hashes, tallest (highest set bit of the leaf count) first.
+    pub fn peaks(&self) -> Vec<HashOutput> {
+        height_bits(self.leaf_count)
+            .map(|h| *self.levels[h].last().expect("bit set implies a pending peak"))
+            .collect()
+    }
+
+    /// Bags the current peaks right-to-left into the accumulator's root.
+    pub fn root(&self) -> HashOutput {
+        bag(&self.peaks()).unwrap_or_default()
+    }
+
+    /// A durable snapshot of this accumulator, suitable for `write_to_file`.
+    pub fn snapshot(&self) -> MmrPeaks {
+        MmrPeaks {
+            leaf_count: self.leaf_count,
+            peaks: self.peaks(),
+        }
+    }
+
+    /// Builds an inclusion proof for `leaf_index`, or `None` if it is out of range or this
+    /// accumulator was rebuilt from a snapshot (see [`Mmr::from_peaks`]).
+    pub fn inclusion_proof(&self, leaf_index: u64) -> Option<InclusionProof> {
+        if !self.supports_proofs || leaf_index >= self.leaf_count {
+            return None;
+        }
+
+        let mut leaves_before = 0u64;
+        let mut peak_height = None;
+        for h in height_bits(self.leaf_count) {
+            let width = 1u64 << h;
+            if leaf_index < leaves_before + width {
+                peak_height = Some(h);
+                break;
+            }
+            leaves_before += width;
+        }
+        let peak_height = peak_height?;
+
+        let mut siblings = Vec::with_capacity(peak_height);
+        let mut index = leaf_index - leaves_before;
+        for h in 0..peak_height {
+            let base = (leaves_before >> h) as usize;
+            let side = if index % 2 == 0 { Side::Right } else { Side::Left };
+            let sibling_index = base + (index ^ 1) as usize;
+            siblings.push((side, self.levels[h][sibling_index]));
+            index /= 2;
+        }
+
+        let peaks_with_widths: Vec<(HashOutput, u64)> = self
+            .peaks()
+            .into_iter()
+            .zip(height_bits(self.leaf_count))
+            .map(|(p, h)| (p, 1u64 << h))
+            .collect();
+        let peak_index = height_bits(self.leaf_count).position(|h| h == peak_height)?;
+        let peak_bagging = bagging_path(&peaks_with_widths, peak_index);
+
+        Some(InclusionProof {
+            siblings,
+            peak_bagging,
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<B: Backend> Mmr<B> {
+    /// Persists the current peak list (see [`Mmr::snapshot`]) so the accumulator can be
+    /// resumed with [`Mmr::load`] after a restart.
+    pub fn save(&self, path: &std::path::Path) -> anyhow::Result<()> {
+        crate::util::write_to_file(path, &self.snapshot())
+    }
+
+    /// Loads an accumulator previously persisted with [`Mmr::save`].
+    pub fn load(path: &std::path::Path) -> anyhow::Result<Self> {
+        let snapshot = crate::util::read_from_file(path)?;
+        Ok(Self::from_peaks(snapshot))
+    }
+}
+
+/// Stateless check that `commitment` at `leaf_index` is included in the accumulator whose
+/// current root is `root`.
+///
+/// `proof.siblings`' `Side`s encode `leaf_index`'s position *within* its peak (a
+/// perfectly-balanced subtree of `2^siblings.len()` leaves), but that peak can start at any
+/// multiple of its width, not just zero -- so the within-peak bits alone don't pin down
+/// `leaf_index` (a proof for leaf 3 of a width-4 peak would equally replay for leaf 7, 11,
+/// ... ). `proof.peak_bagging`'s `Side::Left` entries carry the width of every taller peak
+/// that precedes this one, so summing them reconstructs `leaves_before` -- the true leaf
+/// index this peak starts at -- without needing the accumulator's full leaf count.
+pub fn verify_inclusion<B: Backend>(
+    root: HashOutput,
+    commitment: &Commitment<B>,
+    leaf_index: u64,
+    proof: &InclusionProof,
+) -> bool {
+    let width = 1u64 << proof.siblings.len();
+    let leaves_before: u64 = proof
+        .peak_bagging
+        .iter()
+        .filter(|&&(side, _, _)| side == Side::Left)
+        .map(|&(_, _, w)| w)
+        .sum();
+    if leaf_index < leaves_before || leaf_index - leaves_before >= width {
+        return false;
+    }
+    let mut index = leaf_index - leaves_before;
+
+    let mut acc = leaf_hash::<B>(commitment);
+    for &(side, sibling) in &proof.siblings {
+        let expected_side = if index % 2 == 0 { Side::Right } else { Side::Left };
+        if side != expected_side {
+            return false;
+        }
+        acc = combine(side, sibling, acc);
+        index /= 2;
+    }
+    for &(side, other, _) in &proof.peak_bagging {
+        acc = combine(side, other, acc);
+    }
+    acc == root
+}
+
+#[cfg(test)]
+mod tests {
+    use kzg_traits::{Fr, G1Mul};
+
+    use super::*;
+    use crate::types::{DefaultBackend, TFr, TG1};
+
+    fn commitments(n: u64) -> Vec<Commitment<DefaultBackend>> {
+        let g = TG1::generator();
+        (0..n).map(|i| g.mul(&TFr::from_u64(i + 1))).collect()
+    }
+
+    #[test]
+    fn test_root_changes_deterministically() {
+        let mut mmr = Mmr::<DefaultBackend>::new();
+        let mut roots = Vec::new();
+        for c in commitments(5) {
+            mmr.append(&c);
+            roots.push(mmr.root());
+        }
+        // Every append must change the root, and re-running the same appends must reproduce it.
+        for pair in roots.windows(2) {
+            assert_ne!(pair[0], pair[1]);
+        }
+
+        let mut replay = Mmr::<DefaultBackend>::new();
+        for c in commitments(5) {
+            replay.append(&c);
+        }
+        assert_eq!(replay.root(), *roots.last().unwrap());
+    }
+
+    #[test]
+    fn test_inclusion_proof_roundtrip() {
+        let leaves = commitments(7);
+        let mut mmr = Mmr::<DefaultBackend>::new();
+        for c in &leaves {
+            mmr.append(c);
+        }
+        let root = mmr.root();
+
+        for (i, c) in leaves.iter().enumerate() {
+            let proof = mmr.inclusion_proof(i as u64).expect("proof");
+            assert!(verify_inclusion::<DefaultBackend>(root, c, i as u64, &proof));
+        }
+    }
+
+    #[test]
+    fn test_inclusion_proof_rejects_wrong_commitment() {
+        let leaves = commitments(4);
+        let mut mmr = Mmr::<DefaultBackend>::new();
+        for c in &leaves {
+            mmr.append(c);
+        }
+        let root = mmr.root();
+
+        let proof = mmr.inclusion_proof(1).expect("proof");
+        assert!(!verify_inclusion::<DefaultBackend>(root, &leaves[2], 1, &proof));
+    }
+
+    #[test]
+    fn test_inclusion_proof_rejects_wrong_leaf_index() {
+        let leaves = commitments(7);
+        let mut mmr = Mmr::<DefaultBackend>::new();
+        for c in &leaves {
+            mmr.append(c);
+        }
+        let root = mmr.root();
+
+        let proof = mmr.inclusion_proof(3).expect("proof");
+        assert!(verify_inclusion::<DefaultBackend>(root, &leaves[3], 3, &proof));
+        assert!(!verify_inclusion::<DefaultBackend>(root, &leaves[3], 2, &proof));
+    }
+
+    #[test]
+    fn test_inclusion_proof_rejects_wrong_peak() {
+        // 12 leaves = 0b1100: a width-8 peak covering [0, 8) and a width-4 peak covering
+        // [8, 12). Leaf 3 and leaf 11 share the same low 3 bits (011), so a proof for leaf 3
+        // must not also verify for leaf_index 11 -- even though its siblings' `Side`s replay
+        // identically, `peak_bagging` must pin leaf 3's peak to leaves_before == 0.
+        let leaves = commitments(12);
+        let mut mmr = Mmr::<DefaultBackend>::new();
+        for c in &leaves {
+            mmr.append(c);
+        }
+        let root = mmr.root();
+
+        let proof = mmr.inclusion_proof(3).expect("proof");
+        assert!(verify_inclusion::<DefaultBackend>(root, &leaves[3], 3, &proof));
+        assert!(!verify_inclusion::<DefaultBackend>(root, &leaves[3], 11, &proof));
+
+        // Leaf 9 (in the width-4 peak, leaves_before == 8) shares its low 2 bits with leaf 1
+        // (in the width-8 peak, leaves_before == 0); the wrong-peak claim must fail too.
+        let proof = mmr.inclusion_proof(9).expect("proof");
+        assert!(verify_inclusion::<DefaultBackend>(root, &leaves[9], 9, &proof));
+        assert!(!verify_inclusion::<DefaultBackend>(root, &leaves[9], 1, &proof));
+    }
+
+    #[test]
+    fn test_snapshot_resumes_root_and_append() {
+        let leaves = commitments(6);
+        let mut mmr = Mmr::<DefaultBackend>::new();
+        for c in &leaves {
+            mmr.append(c);
+        }
+        let snapshot = mmr.snapshot();
+
+        let mut direct = Mmr::<DefaultBackend>::new();
+        for c in &leaves {
+            direct.append(c);
+        }
+
+        let mut resumed = Mmr::<DefaultBackend>::from_peaks(snapshot);
+        assert_eq!(resumed.root(), direct.root());
+
+        let extra = commitments(1);
+        direct.append(&extra[0]);
+        resumed.append(&extra[0]);
+        assert_eq!(resumed.root(), direct.root());
+
+        // A reloaded accumulator doesn't retain sibling history for old leaves.
+        assert!(resumed.inclusion_proof(0).is_none());
+    }
+}
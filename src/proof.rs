@@ -1,42 +1,77 @@
+use alloc::{string::String, vec::Vec};
+
 use itertools::izip;
 use kzg_traits::{Fr, G1Mul, KZGSettings, G1};
+#[cfg(feature = "parallel")]
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
 
 use crate::check;
-use crate::commitment::{get_point, open_all_fk20, Commitment, Opening};
+use crate::commitment::{get_point, open_all_fk20, verify_openings_batched, Commitment, Opening};
+use crate::debug_log;
+use crate::encoding::{bytes_to_field_elements, BYTES_PER_FIELD_ELEMENT};
 use crate::hashing::{derive_indices, individual_hash, pow_pass, prelude, HashOutput, Prelude};
 use crate::schnorr::{PublicKey, Schnorr, SecretKey};
-use crate::types::{TFr, TKZGSettings, TG1};
+use crate::types::{Backend, DefaultBackend};
 use crate::util::bitxor;
 
+/// Number of field elements `byte_len` bytes encode to (see [`crate::encoding`]): one
+/// length-prefix element, `byte_len` bytes packed `BYTES_PER_FIELD_ELEMENT` per element,
+/// at least `mvalue` elements (so `derive_indices` always has `mvalue` distinct positions
+/// to sample from), then rounded up to the next power of two (`Proof::prove`/
+/// `open_all_fk20` require a power-of-two number of field elements).
+pub fn padded_chunk_count(byte_len: usize, mvalue: usize) -> usize {
+    let data_chunks = byte_len.div_ceil(BYTES_PER_FIELD_ELEMENT);
+    (data_chunks + 1).max(mvalue).next_power_of_two()
+}
+
 // TODO include beacon
 /// A single Fischlin iteration of the beholder signature
-#[derive(Debug)]
-pub struct BaseProof {
-    schnorr: Schnorr, // (a, c, z)
-    data: Vec<TFr>,
-    openings: Vec<Opening>,
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct BaseProof<B: Backend> {
+    schnorr: Schnorr<B>, // (a, c, z)
+    data: Vec<B::Fr>,
+    openings: Vec<Opening<B>>,
 }
 
 /// A complete beholder signature
-#[derive(Debug)]
-pub struct Proof {
-    pub base_proofs: Vec<BaseProof>,
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Proof<B: Backend = DefaultBackend> {
+    pub base_proofs: Vec<BaseProof<B>>,
+    /// True length of the original data, in bytes, before it was encoded and zero-padded to
+    /// a power-of-two number of field elements.
+    pub original_len: usize,
 }
 
-impl Proof {
-    fn prelude(&self, pk: &PublicKey, com: &Commitment) -> Prelude {
+impl<B: Backend> Proof<B> {
+    fn prelude(&self, pk: &PublicKey<B>, com: &Commitment<B>) -> Prelude {
         let a_i = self.base_proofs.iter().map(|x| x.schnorr.a);
         prelude(pk, com, a_i)
     }
 
+    /// Number of independent Fischlin transcripts (`nfisch`) in this signature.
+    pub fn num_base_proofs(&self) -> usize {
+        self.base_proofs.len()
+    }
+
+    /// Number of challenged indices per transcript (the `mvalue` parameter).
+    pub fn mvalue(&self) -> usize {
+        self.base_proofs.first().map_or(0, |bp| bp.data.len())
+    }
+
+    /// Number of power-of-two field elements `original_len` was encoded and padded to.
+    pub fn padded_chunks(&self) -> usize {
+        padded_chunk_count(self.original_len, self.mvalue())
+    }
+
     /// Verifies the Beholder Signature.
     ///
     /// # Arguments
     ///
     /// * `pk` - Schnorr public key.
     /// * `com` - KZG commitment for the data.
-    /// * `data_len` - Length of the underlying data.
     /// * `kzg_settings` - KZG trusted setup.
     /// * `difficulty` - The bit difficulty, i.e., the required number of leading zeros.
     ///
@@ -45,13 +80,13 @@ impl Proof {
     /// An error is return in case of a KZG error. Otherwise, returns `true` if the verification is successful.
     pub fn verify(
         &self,
-        pk: &PublicKey,
-        com: &Commitment,
-        data_len: usize,
-        kzg_settings: &TKZGSettings,
+        pk: &PublicKey<B>,
+        com: &Commitment<B>,
+        kzg_settings: &B::KZGSettings,
         difficulty: u32,
         mvalue: usize,
     ) -> Result<bool, String> {
+        let data_len = self.padded_chunks();
         let prelude = self.prelude(pk, com);
         for (i, base_proof) in self.base_proofs.iter().enumerate() {
             if !base_proof.verify(
@@ -64,7 +99,7 @@ impl Proof {
                 difficulty,
                 mvalue,
             )? {
-                println!("Failed at base proof {}", i);
+                debug_log!("Failed at base proof {}", i);
                 return Ok(false);
             }
         }
@@ -78,43 +113,47 @@ impl Proof {
     ///
     /// * `kzg_settings` - KZG trusted setup.
     /// * `sk` - Schnorr secret key.
-    /// * `data` - The data to be proven. The length of the data must be a power of two and is assumed to be error-corrected.
+    /// * `data` - The data to be proven, of any length. It is encoded into canonical field
+    ///   elements (see [`crate::encoding`]) and zero-padded up to a power-of-two count; the
+    ///   true length is kept in [`Proof::original_len`] so a verifier can tell padding from
+    ///   real data.
     /// * `nfisch` - Number of Fischlin proofs to generate.
     /// * `difficulty` - The bit difficulty, i.e., the required number of leading zeros.
     ///
+    /// With the `parallel` feature (on by default, matching the rust-kzg backends), the
+    /// `nfisch` transcripts are mined concurrently across a rayon thread pool; without it,
+    /// they are mined one at a time on the current thread, which is slower but avoids the
+    /// std-only rayon dependency (needed for the `no_std` core to build at all).
+    ///
     /// # Returns
     ///
-    /// Returns `Ok(Some(Self))` if the proof generation is successful, `Ok(None)` if it fails,
-    /// or an `Err` with a string message in case of an error.
+    /// Returns the KZG commitment for the (padded) data alongside `Ok(Some(Self))` if the
+    /// proof generation is successful, `Ok(None)` if it fails, or an `Err` with a string
+    /// message in case of a KZG error.
     pub fn prove(
-        kzg_settings: &TKZGSettings,
-        sk: SecretKey,
+        kzg_settings: &B::KZGSettings,
+        sk: SecretKey<B>,
         data: &[u8],
         nfisch: usize,
         difficulty: u32,
         mvalue: usize,
-    ) -> Result<Option<Self>, String> {
-        assert!(
-            data.len().is_power_of_two(),
-            "Data length must be a power of two"
-        );
-
-        let data: Vec<_> = data
-            .chunks_exact(32)
-            .map(|x| TFr::from_bytes_unchecked(x).unwrap())
-            .collect();
+    ) -> Result<(Option<Self>, Commitment<B>), String> {
+        let original_len = data.len();
+        let data: Vec<B::Fr> =
+            bytes_to_field_elements(data, padded_chunk_count(original_len, mvalue));
 
-        let generator = TG1::generator();
+        let generator = B::G1::generator();
         // Compute the openings-
-        let (com, openings) = open_all_fk20(kzg_settings, &data)?;
+        let (com, openings) = open_all_fk20::<B>(kzg_settings, &data)?;
 
         // Compute the Schnorr commitment
-        let r_i: Vec<_> = (0..nfisch).map(|_| TFr::rand()).collect();
+        let r_i: Vec<_> = (0..nfisch).map(|_| B::Fr::rand()).collect();
         let a_i = r_i.iter().map(|r| generator.mul(r));
 
         let pk = generator.mul(&sk);
-        let prelude = prelude(&pk, &com, a_i);
+        let prelude = prelude::<B>(&pk, &com, a_i);
 
+        #[cfg(feature = "parallel")]
         let proofs: Option<Vec<_>> = (0..nfisch)
             .into_par_iter()
             .map(|fisch_iter| {
@@ -130,25 +169,44 @@ impl Proof {
                 )
             })
             .collect();
-        Ok(proofs.map(|base_proofs| Self { base_proofs }))
+        #[cfg(not(feature = "parallel"))]
+        let proofs: Option<Vec<_>> = (0..nfisch)
+            .map(|fisch_iter| {
+                BaseProof::prove(
+                    fisch_iter,
+                    prelude,
+                    &openings,
+                    &r_i[fisch_iter],
+                    &sk,
+                    &data,
+                    difficulty,
+                    mvalue,
+                )
+            })
+            .collect();
+        let proof = proofs.map(|base_proofs| Self {
+            base_proofs,
+            original_len,
+        });
+        Ok((proof, com))
     }
 }
 
-impl BaseProof {
+impl<B: Backend> BaseProof<B> {
     fn verify(
         &self,
         fisch_iter: usize,
         prelude: Prelude,
-        pk: &PublicKey,
-        com: &Commitment,
+        pk: &PublicKey<B>,
+        com: &Commitment<B>,
         data_len: usize,
-        kzg_settings: &TKZGSettings,
+        kzg_settings: &B::KZGSettings,
         difficulty: u32,
         mvalue: usize,
     ) -> Result<bool, String> {
         let fft_settings = kzg_settings.get_fft_settings();
 
-        println!("Checking Schnorr");
+        debug_log!("Checking Schnorr");
         check!(self.schnorr.verify(pk));
 
         // Compute the indices as a Vec<usize>
@@ -161,66 +219,108 @@ impl BaseProof {
         //let indices: [usize; mvalue] = indices.try_into().expect("invalid num_indices");
 
         let mut hash = HashOutput::default();
+        let mut batch = Vec::with_capacity(mvalue);
 
         assert_eq!(self.data.len(), self.openings.len());
-        // Verify openings and accumulate PoW
+        // Accumulate PoW and collect openings for a single batched KZG check
         for ((k, idx), value, opening) in
             izip!(indices.into_iter().enumerate(), &self.data, &self.openings)
         {
             let k = k.try_into().unwrap();
             let x = get_point(fft_settings, data_len, idx);
-
-            check!(kzg_settings.check_proof_single(com, opening, x, value)?);
+            batch.push((*x, *value, *opening));
 
             let partial_pow =
-                individual_hash(prelude, &self.schnorr, fisch_iter, k, *value, opening);
+                individual_hash::<B>(prelude, &self.schnorr, fisch_iter, k, *value, opening);
             hash = bitxor(hash, partial_pow);
         }
 
+        // Verify all openings against `com` with one pairing check
+        check!(verify_openings_batched::<B>(
+            kzg_settings,
+            com,
+            prelude,
+            &self.schnorr,
+            &batch
+        )?);
+
         // Check PoW
         check!(pow_pass(&hash, difficulty));
 
         Ok(true)
     }
 
-    pub fn prove(
+    /// Tries nonce `c` for this transcript, returning the finished [`BaseProof`] if its
+    /// proof-of-work passes at `difficulty`.
+    fn try_nonce(
         fisch_iter: usize,
         prelude: Prelude,
-        openings: &[Opening],
-        r: &TFr,
-        sk: &SecretKey,
-        data: &[TFr],
+        openings: &[Opening<B>],
+        r: &B::Fr,
+        sk: &SecretKey<B>,
+        data: &[B::Fr],
         difficulty: u32,
         mvalue: usize,
+        c: u32,
     ) -> Option<Self> {
-        assert_eq!(data.len(), openings.len());
-        let maxc = 1u32 << (difficulty + 5);
-        for c in 0..maxc {
-            let schnorr = Schnorr::prove(sk, r, c);
+        let schnorr = Schnorr::prove(sk, r, c);
 
-            let indices = derive_indices(fisch_iter, c, mvalue, data.len());
-            let indices: [usize; 16] = indices.try_into().expect("FIXME support m != 16");
-            let data: Vec<_> = indices.iter().map(|&i| data[i]).collect();
-            let openings: Vec<_> = indices.iter().map(|&i| &openings[i]).collect();
+        let indices = derive_indices(fisch_iter, c, mvalue, data.len());
+        let data: Vec<_> = indices.iter().map(|&i| data[i]).collect();
+        let openings: Vec<_> = indices.iter().map(|&i| &openings[i]).collect();
 
-            let mut hash = HashOutput::default();
-            for (k, (val, opening)) in izip!(data.iter(), openings.iter()).enumerate() {
-                let k = k.try_into().unwrap();
-                let partial_pow = individual_hash(prelude, &schnorr, fisch_iter, k, *val, *opening);
+        let mut hash = HashOutput::default();
+        for (k, (val, opening)) in izip!(data.iter(), openings.iter()).enumerate() {
+            let k = k.try_into().unwrap();
+            let partial_pow =
+                individual_hash::<B>(prelude, &schnorr, fisch_iter, k, *val, *opening);
 
-                hash = bitxor(hash, partial_pow);
-            }
-            if pow_pass(&hash, difficulty) {
-                let openings: Vec<_> = openings.into_iter().copied().collect();
-                return Some(BaseProof {
-                    schnorr,
-                    data,
-                    openings,
-                });
-            }
+            hash = bitxor(hash, partial_pow);
         }
+        if pow_pass(&hash, difficulty) {
+            let openings: Vec<_> = openings.into_iter().copied().collect();
+            Some(BaseProof {
+                schnorr,
+                data,
+                openings,
+            })
+        } else {
+            None
+        }
+    }
 
-        None
+    /// Searches for a nonce `c` whose proof-of-work passes at `difficulty`, returning the
+    /// first one found.
+    ///
+    /// With the `parallel` feature (on by default, matching the rust-kzg backends), the
+    /// search scans candidate nonces concurrently across a rayon thread pool; without it,
+    /// nonces are tried in order on the current thread, which is slower but deterministic
+    /// about which nonce is returned when several pass.
+    pub fn prove(
+        fisch_iter: usize,
+        prelude: Prelude,
+        openings: &[Opening<B>],
+        r: &B::Fr,
+        sk: &SecretKey<B>,
+        data: &[B::Fr],
+        difficulty: u32,
+        mvalue: usize,
+    ) -> Option<Self> {
+        assert_eq!(data.len(), openings.len());
+        let maxc = 1u32 << (difficulty + 5);
+
+        #[cfg(feature = "parallel")]
+        {
+            (0..maxc).into_par_iter().find_map_any(|c| {
+                Self::try_nonce(fisch_iter, prelude, openings, r, sk, data, difficulty, mvalue, c)
+            })
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            (0..maxc).find_map(|c| {
+                Self::try_nonce(fisch_iter, prelude, openings, r, sk, data, difficulty, mvalue, c)
+            })
+        }
     }
 }
 
@@ -233,36 +333,36 @@ mod tests {
     use kzg_traits::FFTSettings;
 
     use crate::commitment::interpolate;
+    use crate::types::TG1;
 
     use super::*;
     const M: usize = 16;
 
     #[test]
     fn test_base_proof() {
-        let data: Vec<TFr> = vec![4, 2137, 383, 4]
-            .into_iter()
-            .map(TFr::from_u64)
-            .collect();
+        // `derive_indices` needs at least `mvalue` distinct positions to sample from.
+        let data: Vec<_> = (0..M as u64).map(FsFr::from_u64).collect();
 
-        let secrets_len = 15;
+        let secrets_len = 31;
         let (s1, s2, s3) = generate_trusted_setup(secrets_len, [0; 32]);
-        let fs = FsFFTSettings::new(4).unwrap();
+        let fs = FsFFTSettings::new(5).unwrap();
         let kzg_settings: FsKZGSettings = FsKZGSettings::new(&s1, &s2, &s3, &fs, 7).unwrap();
 
-        let (_com, openings) = open_all_fk20(&kzg_settings, &data).expect("openings");
+        let (_com, openings) = open_all_fk20::<DefaultBackend>(&kzg_settings, &data)
+            .expect("openings");
         assert_eq!(openings.len(), data.len());
 
         let g = TG1::generator();
         let r = FsFr::from_u64(1337);
-        let sk = SecretKey::from_u64(2137);
+        let sk = SecretKey::<DefaultBackend>::from_u64(2137);
         let pk = g.mul(&sk);
         let byte_difficulty = 4;
 
         let fisch_iter = 0;
         let prelude = [0; 8];
-        let mvalue: usize = 16;
+        let mvalue: usize = M;
 
-        let proof = BaseProof::prove(
+        let proof = BaseProof::<DefaultBackend>::prove(
             fisch_iter,
             prelude,
             &openings,
@@ -290,39 +390,122 @@ mod tests {
             .expect("KZG error"));
     }
 
+    #[test]
+    fn test_base_proof_tampered_opening_fails() {
+        // `derive_indices` needs at least `mvalue` distinct positions to sample from.
+        let data: Vec<_> = (0..M as u64).map(FsFr::from_u64).collect();
+
+        let secrets_len = 31;
+        let (s1, s2, s3) = generate_trusted_setup(secrets_len, [0; 32]);
+        let fs = FsFFTSettings::new(5).unwrap();
+        let kzg_settings: FsKZGSettings = FsKZGSettings::new(&s1, &s2, &s3, &fs, 7).unwrap();
+
+        let (_com, openings) = open_all_fk20::<DefaultBackend>(&kzg_settings, &data)
+            .expect("openings");
+
+        let g = TG1::generator();
+        let r = FsFr::from_u64(1337);
+        let sk = SecretKey::<DefaultBackend>::from_u64(2137);
+        let pk = g.mul(&sk);
+        let byte_difficulty = 4;
+
+        let fisch_iter = 0;
+        let prelude = [0; 8];
+        let mvalue: usize = M;
+
+        let mut proof = BaseProof::<DefaultBackend>::prove(
+            fisch_iter,
+            prelude,
+            &openings,
+            &r,
+            &sk,
+            &data,
+            byte_difficulty,
+            mvalue,
+        )
+        .expect("No proof found");
+
+        // Tamper with a single claimed value; the batched opening check must now fail.
+        proof.data[0] = proof.data[0].add(&FsFr::one());
+
+        let poly = interpolate(kzg_settings.get_fft_settings(), &data);
+        let com = kzg_settings.commit_to_poly(&poly).expect("commit");
+        assert!(!proof
+            .verify(
+                fisch_iter,
+                prelude,
+                &pk,
+                &com,
+                data.len(),
+                &kzg_settings,
+                byte_difficulty,
+                mvalue
+            )
+            .expect("KZG error"));
+    }
+
     #[test]
     fn test_mining_works() {
         let data = [4; 128]; //, 5, 1, 5, 7];
         let bit_difficulty = 1;
 
-        let secrets_len = 15;
+        // mvalue=16 forces at least 16 padded chunks (see `padded_chunk_count`), so the
+        // trusted setup and FFT domain need to cover that, not just the 128 data bytes.
+        let secrets_len = 31;
         let (s1, s2, s3) = generate_trusted_setup(secrets_len, [0; 32]);
-        let fs = FsFFTSettings::new(4).unwrap();
+        let fs = FsFFTSettings::new(5).unwrap();
         let kzg_settings: FsKZGSettings = FsKZGSettings::new(&s1, &s2, &s3, &fs, 7).unwrap();
 
         let g = TG1::generator();
-        let sk = SecretKey::from_u64(2137);
+        let sk = SecretKey::<DefaultBackend>::from_u64(2137);
         let pk = g.mul(&sk);
 
         let nfisch = 2;
         let mvalue: usize = 16;
-        let proof = Proof::prove(&kzg_settings, sk, &data, nfisch, bit_difficulty, mvalue)
-            .expect("KZG error")
-            .expect("No proof found");
+        let (proof, com) =
+            Proof::<DefaultBackend>::prove(&kzg_settings, sk, &data, nfisch, bit_difficulty, mvalue)
+                .expect("KZG error");
+        let proof = proof.expect("No proof found");
         assert_eq!(proof.base_proofs.len(), nfisch);
+        assert_eq!(proof.original_len, data.len());
         for base_proof in &proof.base_proofs {
             assert_eq!(base_proof.data.len(), M);
             assert!(base_proof.schnorr.verify(&pk));
         }
 
-        let data: Vec<_> = data
-            .chunks_exact(32)
-            .map(|x| TFr::from_bytes_unchecked(x).unwrap())
-            .collect();
-        let poly = interpolate(kzg_settings.get_fft_settings(), &data);
-        let com = kzg_settings.commit_to_poly(&poly).expect("commit");
         assert!(proof
-            .verify(&pk, &com, data.len(), &kzg_settings, bit_difficulty, mvalue)
+            .verify(&pk, &com, &kzg_settings, bit_difficulty, mvalue)
+            .expect("KZG error"));
+    }
+
+    #[test]
+    fn test_proving_pads_non_power_of_two_data() {
+        let data = [7u8; 100]; // not a multiple of BYTES_PER_FIELD_ELEMENT, nor a power of two
+        let bit_difficulty = 1;
+
+        // mvalue=16 forces at least 16 padded chunks (see `padded_chunk_count`), so the
+        // trusted setup and FFT domain need to cover that, not just the 100 data bytes.
+        let secrets_len = 31;
+        let (s1, s2, s3) = generate_trusted_setup(secrets_len, [0; 32]);
+        let fs = FsFFTSettings::new(5).unwrap();
+        let kzg_settings: FsKZGSettings = FsKZGSettings::new(&s1, &s2, &s3, &fs, 7).unwrap();
+
+        let sk = SecretKey::<DefaultBackend>::from_u64(2137);
+        let g = TG1::generator();
+        let pk = g.mul(&sk);
+
+        let nfisch = 2;
+        let mvalue: usize = 16;
+        let (proof, com) =
+            Proof::<DefaultBackend>::prove(&kzg_settings, sk, &data, nfisch, bit_difficulty, mvalue)
+                .expect("KZG error");
+        let proof = proof.expect("No proof found");
+
+        assert_eq!(proof.original_len, data.len());
+        // 100 bytes -> 4 elements of 31 + 1 length prefix -> 5, but mvalue=16 forces 16
+        assert_eq!(proof.padded_chunks(), 16);
+        assert!(proof
+            .verify(&pk, &com, &kzg_settings, bit_difficulty, mvalue)
             .expect("KZG error"));
     }
 }
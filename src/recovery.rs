@@ -0,0 +1,215 @@
+//! Reed–Solomon erasure recovery over the FK20 rate-1/2 code.
+//!
+//! `open_all_fk20` interpolates a degree-`<N` polynomial and (via FK20) evaluates it on a
+//! `2N`-point domain, which is exactly a rate-1/2 Reed–Solomon codeword. Given any `N` of
+//! those `2N` coded evaluations, [`recover_chunks`] reconstructs the original `N` data
+//! chunks with the standard KZG/FFT erasure decode: build the vanishing polynomial `Z` of
+//! the missing evaluations (the product of `(x - omega^j)` over each missing `j`, so
+//! `deg(Z)` is exactly the number of missing evaluations), form `E = D * Z` pointwise
+//! (well-defined everywhere since both factors are zero at the missing positions), divide
+//! `E / Z` on a coset shift of the domain to dodge those zeros, and read the original
+//! polynomial back off.
+
+use alloc::{format, string::String, vec::Vec};
+
+use kzg_traits::{FFTFr, FFTSettings, Fr, Poly};
+
+use crate::commitment::get_point;
+
+/// Multiplicative shift used to move the division off the domain's own zero set.
+///
+/// 7 is not an odd-order root of unity of any domain size used here, which is all a coset
+/// shift needs to be.
+fn coset_shift<TFr: Fr>() -> TFr {
+    TFr::from_u64(7)
+}
+
+/// Multiplies `coeffs[i]` by `shift^i`, moving an evaluation from the plain domain to (or
+/// back from) the coset shifted by `shift`.
+fn shift_coeffs<TFr: Fr>(coeffs: &[TFr], shift: &TFr) -> Vec<TFr> {
+    let mut power = TFr::one();
+    coeffs
+        .iter()
+        .map(|c| {
+            let shifted = c.mul(&power);
+            power = power.mul(shift);
+            shifted
+        })
+        .collect()
+}
+
+/// Multiplies a polynomial (low-to-high coefficients) by the monic linear factor
+/// `(x - root)`, growing its degree by exactly one.
+fn mul_linear<TFr: Fr>(coeffs: &[TFr], root: &TFr) -> Vec<TFr> {
+    let len = coeffs.len();
+    (0..=len)
+        .map(|k| {
+            let hi = if k >= 1 { coeffs[k - 1] } else { TFr::zero() };
+            let lo = if k < len { coeffs[k].mul(root) } else { TFr::zero() };
+            hi.add(&lo.negate())
+        })
+        .collect()
+}
+
+/// Builds the vanishing polynomial (in coefficient form) of the missing positions in
+/// `known`, as the product of `(x - omega^j)` over each missing index `j` on the
+/// `n2`-point domain. Its degree is exactly the number of missing evaluations.
+fn vanishing_poly<TFr, TFFT>(settings: &TFFT, known: &[Option<TFr>], n2: usize) -> Vec<TFr>
+where
+    TFr: Fr,
+    TFFT: FFTSettings<TFr>,
+{
+    let mut coeffs: Vec<TFr> = vec![TFr::one()];
+    for (j, _) in known.iter().enumerate().filter(|(_, x)| x.is_none()) {
+        let root = get_point(settings, n2, j);
+        coeffs = mul_linear(&coeffs, root);
+    }
+    coeffs.resize(n2, TFr::zero());
+    coeffs
+}
+
+/// Reconstructs the `N` original data chunks from any `N` of the `2N` FK20-coded
+/// evaluations in `known`.
+///
+/// `settings` must be sized for the full `2N`-point domain (as used to extend the data
+/// polynomial via FK20). Errors if fewer than `N` evaluations are known.
+pub fn recover_chunks<TFr, TFFT, TPoly>(
+    settings: &TFFT,
+    known: &[Option<TFr>],
+) -> Result<Vec<TFr>, String>
+where
+    TFr: Fr,
+    TFFT: FFTSettings<TFr> + FFTFr<TFr>,
+    TPoly: Poly<TFr>,
+{
+    let n2 = known.len();
+    if !n2.is_power_of_two() {
+        return Err(format!(
+            "Number of coded evaluations must be a power of two, got {n2}"
+        ));
+    }
+    let n = n2 / 2;
+
+    let known_count = known.iter().filter(|x| x.is_some()).count();
+    if known_count < n {
+        return Err(format!(
+            "Not enough evaluations to recover: need at least {n}, have {known_count}"
+        ));
+    }
+
+    // D: known evaluations, zero at every missing position.
+    let d: Vec<TFr> = known.iter().map(|x| x.unwrap_or_else(TFr::zero)).collect();
+
+    // Z: the vanishing polynomial of the missing positions (degree = number missing <= n),
+    // not the 0/1 indicator (degree up to n2 - 1) -- otherwise `deg(D * Z)` can reach `2n`
+    // and the degree-`<2n` interpolant used below silently corrupts the recovered data.
+    let z_coeffs = vanishing_poly(settings, known, n2);
+    let z_evals = settings.fft_fr(&z_coeffs, false)?;
+
+    // E = D * Z pointwise: zero wherever an evaluation is missing, so it's well-defined
+    // even though D itself isn't known there.
+    let e_evals: Vec<TFr> = d.iter().zip(&z_evals).map(|(d, z)| d.mul(z)).collect();
+    let e_coeffs = settings.fft_fr(&e_evals, true)?;
+
+    // Evaluate both on a coset so dividing E/Z pointwise never hits one of Z's domain zeros.
+    let shift = coset_shift::<TFr>();
+    let z_coset_evals = settings.fft_fr(&shift_coeffs(&z_coeffs, &shift), false)?;
+    let e_coset_evals = settings.fft_fr(&shift_coeffs(&e_coeffs, &shift), false)?;
+
+    let mut p_coset_evals = Vec::with_capacity(n2);
+    for (e, z) in e_coset_evals.iter().zip(&z_coset_evals) {
+        if z.is_zero() {
+            return Err("Z unexpectedly vanished on the coset".into());
+        }
+        p_coset_evals.push(e.mul(&z.inverse()));
+    }
+
+    // Shift back to the plain domain's coefficients: the low N of these are p's, the rest
+    // are zero for a consistent codeword.
+    let p_coset_coeffs = settings.fft_fr(&p_coset_evals, true)?;
+    let inv_shift = shift.inverse();
+    let p_coeffs = shift_coeffs(&p_coset_coeffs, &inv_shift);
+
+    let poly = TPoly::from_coeffs(&p_coeffs[..n]);
+    Ok((0..n)
+        .map(|i| poly.eval(get_point(settings, n, i)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use kzg::types::{fft_settings::FsFFTSettings, fr::FsFr, poly::FsPoly};
+    use kzg_traits::FFTSettings;
+
+    use super::*;
+    use crate::commitment::interpolate;
+
+    fn encode(data: &[FsFr], fs: &FsFFTSettings) -> Vec<FsFr> {
+        let poly: FsPoly = interpolate(fs, data);
+        let mut coeffs = poly.coeffs.clone();
+        coeffs.resize(2 * data.len(), FsFr::zero());
+        fs.fft_fr(&coeffs, false).expect("forward FFT")
+    }
+
+    #[test]
+    fn test_recover_with_no_erasures() {
+        let data: Vec<FsFr> = vec![4, 2137, 383, 4].into_iter().map(FsFr::from_u64).collect();
+        let fs = FsFFTSettings::new(4).unwrap();
+
+        let coded = encode(&data, &fs);
+        let known: Vec<_> = coded.into_iter().map(Some).collect();
+
+        let recovered = recover_chunks::<_, _, FsPoly>(&fs, &known).expect("recovery");
+        assert_eq!(recovered, data);
+    }
+
+    #[test]
+    fn test_recover_with_half_missing() {
+        let data: Vec<FsFr> = vec![4, 2137, 383, 4].into_iter().map(FsFr::from_u64).collect();
+        let fs = FsFFTSettings::new(4).unwrap();
+
+        let coded = encode(&data, &fs);
+        // Drop every other evaluation -- exactly N of the 2N survive.
+        let known: Vec<_> = coded
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| if i % 2 == 0 { Some(x) } else { None })
+            .collect();
+
+        let recovered = recover_chunks::<_, _, FsPoly>(&fs, &known).expect("recovery");
+        assert_eq!(recovered, data);
+    }
+
+    #[test]
+    fn test_recover_with_contiguous_missing() {
+        let data: Vec<FsFr> = vec![4, 2137, 383, 4].into_iter().map(FsFr::from_u64).collect();
+        let fs = FsFFTSettings::new(4).unwrap();
+
+        let coded = encode(&data, &fs);
+        // Drop the entire second half -- the 0/1 indicator of this pattern has degree
+        // n2 - 1, which the naive (non-vanishing) `Z` couldn't handle.
+        let known: Vec<_> = coded
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| if i < data.len() { Some(x) } else { None })
+            .collect();
+
+        let recovered = recover_chunks::<_, _, FsPoly>(&fs, &known).expect("recovery");
+        assert_eq!(recovered, data);
+    }
+
+    #[test]
+    fn test_recover_fails_with_too_few_evaluations() {
+        let data: Vec<FsFr> = vec![4, 2137, 383, 4].into_iter().map(FsFr::from_u64).collect();
+        let fs = FsFFTSettings::new(4).unwrap();
+
+        let coded = encode(&data, &fs);
+        let mut known: Vec<_> = coded.into_iter().map(Some).collect();
+        // Drop one more than is recoverable.
+        for slot in known.iter_mut().take(data.len() + 1) {
+            *slot = None;
+        }
+
+        assert!(recover_chunks::<_, _, FsPoly>(&fs, &known).is_err());
+    }
+}
@@ -1,30 +1,32 @@
-use crate::types::{TFr, TG1};
-use kzg_traits::{Fr, G1Mul, G1};
+use kzg_traits::{EcBackend, Fr, G1Mul, G1};
 use serde::{Deserialize, Serialize};
 
+use crate::types::DefaultBackend;
+
+/// Public key for Schnorr signature
+pub type PublicKey<B = DefaultBackend> = <B as EcBackend>::G1;
 /// Secret key for Schnorr signature
-pub type PublicKey = TG1;
-/// Secret key for Schnorr signature
-pub type SecretKey = TFr;
+pub type SecretKey<B = DefaultBackend> = <B as EcBackend>::Fr;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub(crate) struct Schnorr {
-    pub(crate) a: TG1,
+#[serde(bound = "")]
+pub(crate) struct Schnorr<B: EcBackend> {
+    pub(crate) a: B::G1,
     pub(crate) c: u32,
-    pub(crate) z: TFr,
+    pub(crate) z: B::Fr,
 }
 
-impl Schnorr {
-    pub fn verify(&self, pk: &PublicKey) -> bool {
-        let g = TG1::generator();
-        let c = TFr::from_u64(self.c.into());
+impl<B: EcBackend> Schnorr<B> {
+    pub fn verify(&self, pk: &PublicKey<B>) -> bool {
+        let g = B::G1::generator();
+        let c = B::Fr::from_u64(self.c.into());
         pk.mul(&c).add(&self.a) == g.mul(&self.z)
     }
 
-    pub fn prove(sk: &TFr, r: &TFr, c: u32) -> Self {
-        let cfr = TFr::from_u64(c.into());
+    pub fn prove(sk: &B::Fr, r: &B::Fr, c: u32) -> Self {
+        let cfr = B::Fr::from_u64(c.into());
 
-        let g = TG1::generator();
+        let g = B::G1::generator();
         let a = g.mul(r);
         let z = r.add(&cfr.mul(sk));
         Self { a, c, z }
@@ -38,6 +40,7 @@ pub fn maxc(difficulty: u32) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{DefaultBackend, TG1};
     use kzg::types::fr::FsFr;
 
     #[test]
@@ -48,7 +51,7 @@ mod tests {
         let sk = FsFr::from_u64(42);
         let pk = g.mul(&sk);
         let c = 2137;
-        let proof = Schnorr::prove(&sk, &r, c);
+        let proof = Schnorr::<DefaultBackend>::prove(&sk, &r, c);
         assert!(proof.verify(&pk));
     }
 }
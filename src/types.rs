@@ -1,12 +1,50 @@
 //! Backend-specific types
-use kzg::{eip_7594::BlstBackend, types::fk20_single_settings::FsFK20SingleSettings};
-use kzg_traits::EcBackend;
-
-type Backend = BlstBackend;
-pub type TG1 = <Backend as EcBackend>::G1;
-pub type TG2 = <Backend as EcBackend>::G2;
-pub type TFr = <Backend as EcBackend>::Fr;
-pub type TKZGSettings = <Backend as EcBackend>::KZGSettings;
-pub type TPoly = <Backend as EcBackend>::Poly;
-pub type TFFTSettings = <Backend as EcBackend>::FFTSettings;
-pub type TFK20SingleSettings = FsFK20SingleSettings;
+//!
+//! The cryptographic core is generic over the elliptic-curve backend via [`Backend`], so it
+//! is no longer hardwired to `blst`. [`Backend`] extends `kzg_traits::EcBackend` with the
+//! single-point FK20 opening settings, which upstream wires per-backend rather than through
+//! the `EcBackend` trait itself. [`DefaultBackend`] is the backend the CLI and the `T*` type
+//! aliases bind to; enable a different backend feature to swap it.
+use kzg_traits::{EcBackend, FK20SingleSettings};
+
+#[cfg(feature = "backend-blst")]
+pub use kzg::eip_7594::BlstBackend as DefaultBackend;
+
+/// Extends [`kzg_traits::EcBackend`] with the backend's single-point FK20 opening settings.
+///
+/// rust-kzg doesn't bundle FK20 settings into `EcBackend`, so each supported backend needs a
+/// matching `Backend` impl wiring its own `FK20SingleSettings` type.
+pub trait Backend: EcBackend {
+    type FK20Settings: FK20SingleSettings<
+        Self::Fr,
+        Self::G1,
+        Self::G2,
+        Self::Poly,
+        Self::FFTSettings,
+        Self::KZGSettings,
+    >;
+
+    /// Checks the pairing equation `e(a1, a2) == e(b1, b2)`.
+    ///
+    /// Backs the batched KZG opening check in [`crate::commitment::verify_openings_batched`],
+    /// which needs a raw pairing product rather than the single-opening check
+    /// `KZGSettings::check_proof_single` exposes.
+    fn verify_pairing(a1: &Self::G1, a2: &Self::G2, b1: &Self::G1, b2: &Self::G2) -> bool;
+}
+
+#[cfg(feature = "backend-blst")]
+impl Backend for DefaultBackend {
+    type FK20Settings = kzg::types::fk20_single_settings::FsFK20SingleSettings;
+
+    fn verify_pairing(a1: &Self::G1, a2: &Self::G2, b1: &Self::G1, b2: &Self::G2) -> bool {
+        kzg::utils::pairings_verify(a1, a2, b1, b2)
+    }
+}
+
+pub type TG1 = <DefaultBackend as EcBackend>::G1;
+pub type TG2 = <DefaultBackend as EcBackend>::G2;
+pub type TFr = <DefaultBackend as EcBackend>::Fr;
+pub type TKZGSettings = <DefaultBackend as EcBackend>::KZGSettings;
+pub type TPoly = <DefaultBackend as EcBackend>::Poly;
+pub type TFFTSettings = <DefaultBackend as EcBackend>::FFTSettings;
+pub type TFK20SingleSettings = <DefaultBackend as Backend>::FK20Settings;
@@ -1,8 +1,7 @@
-use std::{fs::File, io::BufReader, ops::BitXor, path::Path};
+use core::ops::BitXor;
 
-use anyhow::Context;
+use alloc::{string::String, vec::Vec};
 use kzg_traits::FFTSettings;
-use serde::{de::DeserializeOwned, Serialize};
 
 use crate::types::TFFTSettings;
 
@@ -18,17 +17,39 @@ pub(crate) fn bitxor<T: BitXor, const N: usize>(
     }
 }
 
+/// Emits a debug line when the `std` feature is enabled, and is a no-op otherwise.
+///
+/// This is the logging hook for the few diagnostic prints in the crypto core, so those
+/// call sites don't have to pull in `std::io` just to report progress.
+#[macro_export]
+macro_rules! debug_log {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "std")]
+        {
+            std::println!($($arg)*);
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! check {
+    ($expr:expr $(,)?) => {
+        if !$expr {
+            return Ok(false);
+        }
+    };
     ($expr:expr, $msg:expr $(,)?) => {
         if !$expr {
-            eprintln!("{}", $msg);
+            $crate::debug_log!("{}", $msg);
             return Ok(false);
         }
     };
 }
 
-pub fn write_to_file<T: Serialize>(path: &std::path::Path, data: &T) -> anyhow::Result<()> {
+#[cfg(feature = "std")]
+pub fn write_to_file<T: serde::Serialize>(path: &std::path::Path, data: &T) -> anyhow::Result<()> {
+    use anyhow::Context;
+
     let file = std::fs::File::create(path).context(format!("Unable to create file: {:?}", path))?;
     let mut writer = std::io::BufWriter::new(file);
     bincode::serde::encode_into_std_write(data, &mut writer, bincode::config::standard())
@@ -36,13 +57,159 @@ pub fn write_to_file<T: Serialize>(path: &std::path::Path, data: &T) -> anyhow::
     Ok(())
 }
 
-pub fn read_from_file<T: DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
-    let file = File::open(path).context(format!("Unable to open file: {:?}", path))?;
-    let mut reader = BufReader::new(file);
+#[cfg(feature = "std")]
+pub fn read_from_file<T: serde::de::DeserializeOwned>(path: &std::path::Path) -> anyhow::Result<T> {
+    use anyhow::Context;
+
+    let file = std::fs::File::open(path).context(format!("Unable to open file: {:?}", path))?;
+    let mut reader = std::io::BufReader::new(file);
     bincode::serde::decode_from_std_read(&mut reader, bincode::config::standard())
         .context("Bincode deserialization error")
 }
 
+/// Envelope magic bytes identifying an encrypted secret-key file.
+const ENCRYPTED_MAGIC: &[u8; 4] = b"BHS1";
+
+/// AEAD algorithm used to seal an encrypted secret-key envelope.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AeadAlgorithm {
+    Aes256Gcm = 0,
+    ChaCha20Poly1305 = 1,
+}
+
+impl AeadAlgorithm {
+    fn from_tag(tag: u8) -> anyhow::Result<Self> {
+        match tag {
+            0 => Ok(Self::Aes256Gcm),
+            1 => Ok(Self::ChaCha20Poly1305),
+            _ => anyhow::bail!("Unknown AEAD algorithm tag: {tag}"),
+        }
+    }
+}
+
+/// Derives a 32-byte AEAD key from a passphrase and salt using Argon2id.
+#[cfg(feature = "std")]
+fn derive_key(passphrase: &str, salt: &[u8; 16]) -> anyhow::Result<[u8; 32]> {
+    use argon2::Argon2;
+
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow::anyhow!("Argon2id key derivation failed: {e}"))?;
+    Ok(key)
+}
+
+/// Writes `data` to `path` as a passphrase-encrypted envelope.
+///
+/// The on-disk layout is `[magic | version | kdf_tag | aead_tag | salt | nonce | ciphertext]`,
+/// where `ciphertext` is the bincode encoding of `data` sealed with AES-256-GCM or
+/// ChaCha20-Poly1305 (selected by `algorithm`) under a key derived from `passphrase` via
+/// Argon2id. Use this for secret material; public keys can keep using [`write_to_file`].
+#[cfg(feature = "std")]
+pub fn write_encrypted_to_file<T: serde::Serialize>(
+    path: &std::path::Path,
+    data: &T,
+    passphrase: &str,
+    algorithm: AeadAlgorithm,
+) -> anyhow::Result<()> {
+    use aead::{Aead, KeyInit, OsRng};
+    use anyhow::Context;
+    use rand::RngCore;
+
+    let plaintext = bincode::serde::encode_to_vec(data, bincode::config::standard())
+        .context("Bincode serialization error")?;
+
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    let key = derive_key(passphrase, &salt)?;
+
+    let mut nonce = [0u8; 12];
+    OsRng.fill_bytes(&mut nonce);
+
+    let ciphertext = match algorithm {
+        AeadAlgorithm::Aes256Gcm => {
+            let cipher = aes_gcm::Aes256Gcm::new_from_slice(&key).expect("key is 32 bytes");
+            cipher
+                .encrypt(aead::Nonce::<aes_gcm::Aes256Gcm>::from_slice(&nonce), plaintext.as_ref())
+                .map_err(|e| anyhow::anyhow!("AES-256-GCM encryption failed: {e}"))?
+        }
+        AeadAlgorithm::ChaCha20Poly1305 => {
+            let cipher =
+                chacha20poly1305::ChaCha20Poly1305::new_from_slice(&key).expect("key is 32 bytes");
+            cipher
+                .encrypt(
+                    aead::Nonce::<chacha20poly1305::ChaCha20Poly1305>::from_slice(&nonce),
+                    plaintext.as_ref(),
+                )
+                .map_err(|e| anyhow::anyhow!("ChaCha20-Poly1305 encryption failed: {e}"))?
+        }
+    };
+
+    let file = std::fs::File::create(path).context(format!("Unable to create file: {:?}", path))?;
+    let mut writer = std::io::BufWriter::new(file);
+    use std::io::Write;
+    writer.write_all(ENCRYPTED_MAGIC)?;
+    writer.write_all(&[1u8])?; // version
+    writer.write_all(&[0u8])?; // kdf_tag: Argon2id
+    writer.write_all(&[algorithm as u8])?;
+    writer.write_all(&salt)?;
+    writer.write_all(&nonce)?;
+    writer.write_all(&ciphertext)?;
+    Ok(())
+}
+
+/// Reads and decrypts a passphrase-encrypted envelope written by [`write_encrypted_to_file`].
+///
+/// A wrong passphrase or a tampered file is reported as a dedicated "bad passphrase or
+/// tampered file" error, distinguishable from a downstream bincode parse failure.
+#[cfg(feature = "std")]
+pub fn read_encrypted_from_file<T: serde::de::DeserializeOwned>(
+    path: &std::path::Path,
+    passphrase: &str,
+) -> anyhow::Result<T> {
+    use aead::{Aead, KeyInit};
+    use anyhow::Context;
+
+    let bytes =
+        std::fs::read(path).context(format!("Unable to read file: {:?}", path))?;
+
+    anyhow::ensure!(bytes.len() >= 4 + 1 + 1 + 1 + 16 + 12, "Truncated secret-key file");
+    anyhow::ensure!(&bytes[0..4] == ENCRYPTED_MAGIC, "Not a beholders encrypted secret-key file");
+    anyhow::ensure!(bytes[4] == 1, "Unsupported secret-key envelope version: {}", bytes[4]);
+    anyhow::ensure!(bytes[5] == 0, "Unknown KDF tag: {}", bytes[5]);
+    let algorithm = AeadAlgorithm::from_tag(bytes[6])?;
+
+    let salt: [u8; 16] = bytes[7..23].try_into().expect("slice is 16 bytes");
+    let nonce: [u8; 12] = bytes[23..35].try_into().expect("slice is 12 bytes");
+    let ciphertext = &bytes[35..];
+
+    let key = derive_key(passphrase, &salt)?;
+
+    let plaintext = match algorithm {
+        AeadAlgorithm::Aes256Gcm => {
+            let cipher = aes_gcm::Aes256Gcm::new_from_slice(&key).expect("key is 32 bytes");
+            cipher
+                .decrypt(aead::Nonce::<aes_gcm::Aes256Gcm>::from_slice(&nonce), ciphertext)
+                .map_err(|_| anyhow::anyhow!("Bad passphrase or tampered secret-key file"))?
+        }
+        AeadAlgorithm::ChaCha20Poly1305 => {
+            let cipher =
+                chacha20poly1305::ChaCha20Poly1305::new_from_slice(&key).expect("key is 32 bytes");
+            cipher
+                .decrypt(
+                    aead::Nonce::<chacha20poly1305::ChaCha20Poly1305>::from_slice(&nonce),
+                    ciphertext,
+                )
+                .map_err(|_| anyhow::anyhow!("Bad passphrase or tampered secret-key file"))?
+        }
+    };
+
+    bincode::serde::decode_from_slice(&plaintext, bincode::config::standard())
+        .map(|(value, _)| value)
+        .context("Bincode deserialization error")
+}
+
 /// Returns the FFT settings for a given data length.
 pub fn fft_settings(chunks: usize) -> Result<TFFTSettings, String> {
     assert!(